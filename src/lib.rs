@@ -5,16 +5,21 @@
 
 //! Binary files with magic numbers and versioning.
 //!
-//! See [`BinFile`] for the details.
+//! See [`BinFile`] for the details. For formats using a different header byte order or magic
+//! width, see [`EndianBinFile`] (aliased as [`BinFileBE`]/[`BinFileLE`]).
 
 #[macro_use]
 extern crate amplify;
 
 use std::fs::{File, OpenOptions};
-use std::io::{self, Read, Write};
-use std::ops::{Deref, DerefMut};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut, RangeInclusive};
 use std::path::Path;
 
+/// The length, in bytes, of the fixed magic+version header written by [`BinFile`].
+const HEADER_LEN: usize = 10;
+
 /// Binary file which ensures it always starts with a given magic byte octet.
 ///
 /// Works as a drop-in replacement for [`File`], which is checking that the file always start with
@@ -38,28 +43,40 @@ use std::path::Path;
 /// BinFile::<MY_MAGIC, 1>::create("target/test").unwrap();
 /// ```
 #[derive(Debug)]
-pub struct BinFile<const MAGIC: u64, const VERSION: u16 = 1>(File);
+pub struct BinFile<const MAGIC: u64, const VERSION: u16 = 1, const HAS_META: bool = false> {
+    file: File,
+    version: u16,
+    metadata: Vec<u8>,
+}
 
-impl<const MAGIC: u64, const VERSION: u16> Deref for BinFile<MAGIC, VERSION> {
+impl<const MAGIC: u64, const VERSION: u16, const HAS_META: bool> Deref
+    for BinFile<MAGIC, VERSION, HAS_META>
+{
     type Target = File;
 
-    fn deref(&self) -> &Self::Target { &self.0 }
+    fn deref(&self) -> &Self::Target { &self.file }
 }
 
-impl<const MAGIC: u64, const VERSION: u16> DerefMut for BinFile<MAGIC, VERSION> {
-    fn deref_mut(&mut self) -> &mut Self::Target { &mut self.0 }
+impl<const MAGIC: u64, const VERSION: u16, const HAS_META: bool> DerefMut
+    for BinFile<MAGIC, VERSION, HAS_META>
+{
+    fn deref_mut(&mut self) -> &mut Self::Target { &mut self.file }
 }
 
-impl<const MAGIC: u64, const VERSION: u16> Read for BinFile<MAGIC, VERSION> {
-    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> { self.0.read(buf) }
+impl<const MAGIC: u64, const VERSION: u16, const HAS_META: bool> Read
+    for BinFile<MAGIC, VERSION, HAS_META>
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> { self.file.read(buf) }
 }
 
-impl<const MAGIC: u64, const VERSION: u16> Write for BinFile<MAGIC, VERSION> {
-    fn write(&mut self, buf: &[u8]) -> io::Result<usize> { self.0.write(buf) }
-    fn flush(&mut self) -> io::Result<()> { self.0.flush() }
+impl<const MAGIC: u64, const VERSION: u16, const HAS_META: bool> Write
+    for BinFile<MAGIC, VERSION, HAS_META>
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> { self.file.write(buf) }
+    fn flush(&mut self) -> io::Result<()> { self.file.flush() }
 }
 
-impl<const MAGIC: u64, const VERSION: u16> BinFile<MAGIC, VERSION> {
+impl<const MAGIC: u64, const VERSION: u16, const HAS_META: bool> BinFile<MAGIC, VERSION, HAS_META> {
     /// The magical byte octet, taken from the generic parameter of the type. It must be a big
     /// endian-serialized octet.
     pub const MAGIC: u64 = MAGIC;
@@ -67,16 +84,26 @@ impl<const MAGIC: u64, const VERSION: u16> BinFile<MAGIC, VERSION> {
     /// The version number, taken from the generic parameter of the type.
     pub const VERSION: u16 = VERSION;
 
+    /// The version that was actually found in the file header.
+    ///
+    /// This is equal to [`Self::VERSION`] unless the file was opened with
+    /// [`Self::open_compatible`] or [`Self::open_rw_compatible`], in which case it reflects
+    /// whatever version, within the accepted range, was stored on disk.
+    pub fn version(&self) -> u16 { self.version }
+}
+
+impl<const MAGIC: u64, const VERSION: u16> BinFile<MAGIC, VERSION, false> {
     /// Opens the file in read-write mode, the same way as [`File::create`] does.
     ///
     /// Creates the file if it doesn't exist, and truncates if it does. In both cases, it writes
     /// the magic number and the version (10 bytes in total) at the start of the file. The produced
     /// file stream will start at byte offset 10.
     pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
-        let mut file = File::create(path)?;
+        let mut file =
+            OpenOptions::new().read(true).write(true).create(true).truncate(true).open(path)?;
         file.write_all(&MAGIC.to_be_bytes())?;
         file.write_all(&VERSION.to_be_bytes())?;
-        Ok(Self(file))
+        Ok(Self { file, version: VERSION, metadata: Vec::new() })
     }
 
     /// Creates a new file in read-write mode; error if the file exists, the same way as
@@ -85,10 +112,10 @@ impl<const MAGIC: u64, const VERSION: u16> BinFile<MAGIC, VERSION> {
     /// Writes the magic number and the version (10 bytes in total) at the start of the file. The
     /// produced file stream will start at byte offset 10.
     pub fn create_new(path: impl AsRef<Path>) -> io::Result<Self> {
-        let mut file = File::create_new(path)?;
+        let mut file = OpenOptions::new().read(true).write(true).create_new(true).open(path)?;
         file.write_all(&MAGIC.to_be_bytes())?;
         file.write_all(&VERSION.to_be_bytes())?;
-        Ok(Self(file))
+        Ok(Self { file, version: VERSION, metadata: Vec::new() })
     }
 
     /// Attempts to open a file in read-only mode the same way as [`File::open`] does.
@@ -97,8 +124,8 @@ impl<const MAGIC: u64, const VERSION: u16> BinFile<MAGIC, VERSION> {
     /// bytes) and version number (2 bytes). The produced file stream will start at byte offset 10.
     pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
         let path = path.as_ref();
-        let mut file = Self(File::open(&path)?);
-        file.check(&path)?;
+        let mut file = Self { file: File::open(path)?, version: 0, metadata: Vec::new() };
+        file.check(path, VERSION..=VERSION)?;
         Ok(file)
     }
 
@@ -109,12 +136,49 @@ impl<const MAGIC: u64, const VERSION: u16> BinFile<MAGIC, VERSION> {
     /// bytes) and version number (2 bytes). The produced file stream will start at byte offset 10.
     pub fn open_rw(path: impl AsRef<Path>) -> io::Result<Self> {
         let path = path.as_ref();
-        let mut file = Self(OpenOptions::new().read(true).write(true).open(&path)?);
-        file.check(&path)?;
+        let mut file = Self {
+            file: OpenOptions::new().read(true).write(true).open(path)?,
+            version: 0,
+            metadata: Vec::new(),
+        };
+        file.check(path, VERSION..=VERSION)?;
         Ok(file)
     }
 
-    fn check(&mut self, filename: &Path) -> io::Result<()> {
+    /// Attempts to open a file in read-only mode, accepting any on-disk version that falls within
+    /// `versions`, rather than requiring an exact match with [`Self::VERSION`].
+    ///
+    /// This allows a caller to open an older-but-compatible file and migrate its contents, by
+    /// inspecting [`Self::version`] after a successful open. Raises [`BinFileError::InvalidVersion`]
+    /// only when the on-disk version falls outside of `versions`.
+    pub fn open_compatible(path: impl AsRef<Path>, versions: RangeInclusive<u16>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let mut file = Self { file: File::open(path)?, version: 0, metadata: Vec::new() };
+        file.check(path, versions)?;
+        Ok(file)
+    }
+
+    /// Attempts to open a file in read-write mode, accepting any on-disk version that falls within
+    /// `versions`, rather than requiring an exact match with [`Self::VERSION`].
+    ///
+    /// This allows a caller to open an older-but-compatible file and migrate its contents, by
+    /// inspecting [`Self::version`] after a successful open. Raises [`BinFileError::InvalidVersion`]
+    /// only when the on-disk version falls outside of `versions`.
+    pub fn open_rw_compatible(
+        path: impl AsRef<Path>,
+        versions: RangeInclusive<u16>,
+    ) -> io::Result<Self> {
+        let path = path.as_ref();
+        let mut file = Self {
+            file: OpenOptions::new().read(true).write(true).open(path)?,
+            version: 0,
+            metadata: Vec::new(),
+        };
+        file.check(path, versions)?;
+        Ok(file)
+    }
+
+    fn check(&mut self, filename: &Path, versions: RangeInclusive<u16>) -> io::Result<()> {
         let mut magic = [0u8; 8];
         self.read_exact(&mut magic)?;
         if magic != MAGIC.to_be_bytes() {
@@ -126,13 +190,384 @@ impl<const MAGIC: u64, const VERSION: u16> BinFile<MAGIC, VERSION> {
         }
         let mut version = [0u8; 2];
         self.read_exact(&mut version)?;
-        if version != VERSION.to_be_bytes() {
+        let version = u16::from_be_bytes(version);
+        if !versions.contains(&version) {
             return Err(io::Error::other(BinFileError::InvalidVersion {
                 filename: filename.to_string_lossy().to_string(),
                 expected: VERSION,
-                actual: u16::from_be_bytes(version),
+                actual: version,
             }));
         }
+        self.version = version;
+        Ok(())
+    }
+}
+
+impl<const MAGIC: u64, const VERSION: u16> BinFile<MAGIC, VERSION, true> {
+    /// Creates the file the same way as [`BinFile::create`] does, additionally writing a
+    /// variable-length metadata block right after the magic+version prefix: a 2-byte big-endian
+    /// length followed by `meta` (e.g. a crate semver string or a bitset of feature flags). The
+    /// payload stream starts right after the metadata block.
+    pub fn create_with_meta(path: impl AsRef<Path>, meta: &[u8]) -> io::Result<Self> {
+        let mut file =
+            OpenOptions::new().read(true).write(true).create(true).truncate(true).open(path)?;
+        file.write_all(&MAGIC.to_be_bytes())?;
+        file.write_all(&VERSION.to_be_bytes())?;
+        file.write_all(&(meta.len() as u16).to_be_bytes())?;
+        file.write_all(meta)?;
+        Ok(Self { file, version: VERSION, metadata: meta.to_vec() })
+    }
+
+    /// Attempts to open a file in read-only mode the same way as [`BinFile::open`] does,
+    /// additionally reading the variable-length metadata block so it is exposed through
+    /// [`Self::metadata`]. Its contents are not validated; use [`Self::open_validated`] to reject
+    /// incompatible metadata.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let mut file = File::open(path)?;
+        let (version, metadata) = Self::check(&mut file, path)?;
+        Ok(Self { file, version, metadata })
+    }
+
+    /// Attempts to open a file in read-write mode the same way as [`BinFile::open_rw`] does,
+    /// additionally reading the variable-length metadata block so it is exposed through
+    /// [`Self::metadata`]. Its contents are not validated; use [`Self::open_rw_validated`] to
+    /// reject incompatible metadata.
+    pub fn open_rw(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let mut file = OpenOptions::new().read(true).write(true).open(path)?;
+        let (version, metadata) = Self::check(&mut file, path)?;
+        Ok(Self { file, version, metadata })
+    }
+
+    /// Same as [`Self::open`], additionally calling `validate` with the embedded metadata and
+    /// returning [`BinFileError::IncompatibleMetadata`] if it returns `false`. Callers use this to
+    /// reject files whose embedded semver/feature flags are incompatible.
+    pub fn open_validated(
+        path: impl AsRef<Path>,
+        validate: impl FnOnce(&[u8]) -> bool,
+    ) -> io::Result<Self> {
+        let path = path.as_ref();
+        let mut file = File::open(path)?;
+        let (version, metadata) = Self::check(&mut file, path)?;
+        if !validate(&metadata) {
+            return Err(io::Error::other(BinFileError::IncompatibleMetadata {
+                filename: path.to_string_lossy().to_string(),
+            }));
+        }
+        Ok(Self { file, version, metadata })
+    }
+
+    /// Same as [`Self::open_rw`], additionally calling `validate` with the embedded metadata and
+    /// returning [`BinFileError::IncompatibleMetadata`] if it returns `false`. Callers use this to
+    /// reject files whose embedded semver/feature flags are incompatible.
+    pub fn open_rw_validated(
+        path: impl AsRef<Path>,
+        validate: impl FnOnce(&[u8]) -> bool,
+    ) -> io::Result<Self> {
+        let path = path.as_ref();
+        let mut file = OpenOptions::new().read(true).write(true).open(path)?;
+        let (version, metadata) = Self::check(&mut file, path)?;
+        if !validate(&metadata) {
+            return Err(io::Error::other(BinFileError::IncompatibleMetadata {
+                filename: path.to_string_lossy().to_string(),
+            }));
+        }
+        Ok(Self { file, version, metadata })
+    }
+
+    /// The variable-length metadata embedded in the file header, as read at open time.
+    pub fn metadata(&self) -> &[u8] { &self.metadata }
+
+    fn check(file: &mut File, filename: &Path) -> io::Result<(u16, Vec<u8>)> {
+        let mut magic = [0u8; 8];
+        file.read_exact(&mut magic)?;
+        if magic != MAGIC.to_be_bytes() {
+            return Err(io::Error::other(BinFileError::InvalidMagic {
+                filename: filename.to_string_lossy().to_string(),
+                expected: MAGIC,
+                actual: u64::from_be_bytes(magic),
+            }));
+        }
+        let mut version = [0u8; 2];
+        file.read_exact(&mut version)?;
+        let version = u16::from_be_bytes(version);
+        if version != VERSION {
+            return Err(io::Error::other(BinFileError::InvalidVersion {
+                filename: filename.to_string_lossy().to_string(),
+                expected: VERSION,
+                actual: version,
+            }));
+        }
+        let mut len = [0u8; 2];
+        file.read_exact(&mut len)?;
+        let mut metadata = vec![0u8; u16::from_be_bytes(len) as usize];
+        file.read_exact(&mut metadata)?;
+        Ok((version, metadata))
+    }
+}
+
+/// Reads the magic number and version from the header of a file without comparing them against
+/// any expected values.
+///
+/// Unlike [`BinFile::open`], which requires the magic and version to be known as const generic
+/// parameters at compile time, this function lets a program inspect the header of a file whose
+/// format it does not yet know, so it can dispatch on the result.
+pub fn peek_header(path: impl AsRef<Path>) -> io::Result<(u64, u16)> {
+    let mut file = File::open(path)?;
+    let mut magic = [0u8; 8];
+    file.read_exact(&mut magic)?;
+    let mut version = [0u8; 2];
+    file.read_exact(&mut version)?;
+    Ok((u64::from_be_bytes(magic), u16::from_be_bytes(version)))
+}
+
+/// A binary file whose magic number and version are read at runtime rather than asserted through
+/// const generic parameters.
+///
+/// Works like [`BinFile`], except that [`RawBinFile::try_open`] performs no validation: it simply
+/// reads the 10-byte header and hands back both the parsed magic/version and the open stream,
+/// positioned right after the header, so that a program which doesn't know the expected format
+/// ahead of time (e.g. an archive unpacker) can decide how to proceed.
+#[derive(Debug)]
+pub struct RawBinFile {
+    file: File,
+    magic: u64,
+    version: u16,
+}
+
+impl Deref for RawBinFile {
+    type Target = File;
+
+    fn deref(&self) -> &Self::Target { &self.file }
+}
+
+impl DerefMut for RawBinFile {
+    fn deref_mut(&mut self) -> &mut Self::Target { &mut self.file }
+}
+
+impl Read for RawBinFile {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> { self.file.read(buf) }
+}
+
+impl Write for RawBinFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> { self.file.write(buf) }
+    fn flush(&mut self) -> io::Result<()> { self.file.flush() }
+}
+
+impl RawBinFile {
+    /// Opens the file in read-only mode and reads its 10-byte header without validating it.
+    ///
+    /// The produced file stream will start at byte offset 10, same as [`BinFile::open`].
+    pub fn try_open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut file = File::open(path)?;
+        let mut magic = [0u8; 8];
+        file.read_exact(&mut magic)?;
+        let mut version = [0u8; 2];
+        file.read_exact(&mut version)?;
+        Ok(Self { file, magic: u64::from_be_bytes(magic), version: u16::from_be_bytes(version) })
+    }
+
+    /// The magic number read from the file header.
+    pub fn magic(&self) -> u64 { self.magic }
+
+    /// The version read from the file header.
+    pub fn version(&self) -> u16 { self.version }
+}
+
+#[cfg(feature = "memmap")]
+impl<const MAGIC: u64, const VERSION: u16> BinFile<MAGIC, VERSION, false> {
+    /// Memory-maps the payload region of the file — i.e. everything after the 10-byte
+    /// magic+version header — for read-only access.
+    ///
+    /// This is useful for read-heavy workloads where streaming the payload through [`Read`] would
+    /// be wasteful. Requires the `memmap` feature.
+    pub fn mmap(&self) -> io::Result<PayloadMap> {
+        let mmap = unsafe { memmap2::Mmap::map(&self.file) }?;
+        if mmap.len() < HEADER_LEN {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "file shorter than its header"));
+        }
+        Ok(PayloadMap { mmap, version: self.version })
+    }
+}
+
+/// A read-only memory mapping of a [`BinFile`]'s payload, with the magic+version header hidden.
+///
+/// Dereferences to `&[u8]` starting right after the header, so callers index it exactly as they
+/// would a plain payload buffer. Requires the `memmap` feature.
+#[cfg(feature = "memmap")]
+#[derive(Debug)]
+pub struct PayloadMap {
+    mmap: memmap2::Mmap,
+    version: u16,
+}
+
+#[cfg(feature = "memmap")]
+impl Deref for PayloadMap {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target { &self.mmap[HEADER_LEN..] }
+}
+
+#[cfg(feature = "memmap")]
+impl PayloadMap {
+    /// The version that was read from the file header when the mapping was created.
+    pub fn version(&self) -> u16 { self.version }
+}
+
+/// The length, in bytes, of the header written by [`ChecksummedBinFile`]: the usual 8-byte magic
+/// and 2-byte version, followed by a 4-byte CRC32 of the payload.
+const CHECKSUM_HEADER_LEN: usize = HEADER_LEN + 4;
+
+/// Binary file which, in addition to the magic number and version validated by [`BinFile`], stores
+/// a CRC32 checksum of the payload in its header and verifies it on open.
+///
+/// This catches silent corruption or truncation that magic+version checks alone cannot detect. The
+/// checksum is rewritten whenever the file is flushed or dropped, by seeking back to the checksum
+/// field after hashing the payload bytes written so far. Existing [`BinFile`] users that don't want
+/// this overhead are unaffected, since it is a distinct type.
+#[derive(Debug)]
+pub struct ChecksummedBinFile<const MAGIC: u64, const VERSION: u16 = 1> {
+    file: File,
+    version: u16,
+}
+
+impl<const MAGIC: u64, const VERSION: u16> Deref for ChecksummedBinFile<MAGIC, VERSION> {
+    type Target = File;
+
+    fn deref(&self) -> &Self::Target { &self.file }
+}
+
+impl<const MAGIC: u64, const VERSION: u16> DerefMut for ChecksummedBinFile<MAGIC, VERSION> {
+    fn deref_mut(&mut self) -> &mut Self::Target { &mut self.file }
+}
+
+impl<const MAGIC: u64, const VERSION: u16> Read for ChecksummedBinFile<MAGIC, VERSION> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> { self.file.read(buf) }
+}
+
+impl<const MAGIC: u64, const VERSION: u16> Write for ChecksummedBinFile<MAGIC, VERSION> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> { self.file.write(buf) }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        self.rewrite_checksum()
+    }
+}
+
+impl<const MAGIC: u64, const VERSION: u16> Drop for ChecksummedBinFile<MAGIC, VERSION> {
+    fn drop(&mut self) { let _ = self.rewrite_checksum(); }
+}
+
+impl<const MAGIC: u64, const VERSION: u16> ChecksummedBinFile<MAGIC, VERSION> {
+    /// The magical byte octet, taken from the generic parameter of the type.
+    pub const MAGIC: u64 = MAGIC;
+
+    /// The version number, taken from the generic parameter of the type.
+    pub const VERSION: u16 = VERSION;
+
+    /// The version that was actually read from the file header.
+    pub fn version(&self) -> u16 { self.version }
+
+    /// Creates the file, the same way as [`BinFile::create`] does, additionally reserving a
+    /// 4-byte checksum field which is populated on the first [`flush`](Write::flush) or drop.
+    ///
+    /// The file is opened read-write, not write-only, since `rewrite_checksum` needs to
+    /// read the payload back in order to hash it.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut file =
+            OpenOptions::new().read(true).write(true).create(true).truncate(true).open(path)?;
+        file.write_all(&MAGIC.to_be_bytes())?;
+        file.write_all(&VERSION.to_be_bytes())?;
+        file.write_all(&0u32.to_be_bytes())?;
+        Ok(Self { file, version: VERSION })
+    }
+
+    /// Creates a new file, the same way as [`BinFile::create_new`] does, additionally reserving a
+    /// 4-byte checksum field which is populated on the first [`flush`](Write::flush) or drop.
+    ///
+    /// The file is opened read-write, not write-only, since `rewrite_checksum` needs to
+    /// read the payload back in order to hash it.
+    pub fn create_new(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut file = OpenOptions::new().read(true).write(true).create_new(true).open(path)?;
+        file.write_all(&MAGIC.to_be_bytes())?;
+        file.write_all(&VERSION.to_be_bytes())?;
+        file.write_all(&0u32.to_be_bytes())?;
+        Ok(Self { file, version: VERSION })
+    }
+
+    /// Opens the file in read-only mode, the same way as [`BinFile::open`] does, additionally
+    /// recomputing the CRC32 of the payload and comparing it against the one stored in the header.
+    ///
+    /// Returns [`BinFileError::ChecksumMismatch`] if the payload was corrupted or truncated.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let mut file = File::open(path)?;
+        let version = Self::check(&mut file, path)?;
+        Ok(Self { file, version })
+    }
+
+    /// Opens the file in read-write mode, the same way as [`BinFile::open_rw`] does, additionally
+    /// recomputing the CRC32 of the payload and comparing it against the one stored in the header.
+    ///
+    /// Returns [`BinFileError::ChecksumMismatch`] if the payload was corrupted or truncated.
+    pub fn open_rw(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let mut file = OpenOptions::new().read(true).write(true).open(path)?;
+        let version = Self::check(&mut file, path)?;
+        Ok(Self { file, version })
+    }
+
+    fn check(file: &mut File, filename: &Path) -> io::Result<u16> {
+        let mut magic = [0u8; 8];
+        file.read_exact(&mut magic)?;
+        if magic != MAGIC.to_be_bytes() {
+            return Err(io::Error::other(BinFileError::InvalidMagic {
+                filename: filename.to_string_lossy().to_string(),
+                expected: MAGIC,
+                actual: u64::from_be_bytes(magic),
+            }));
+        }
+        let mut version = [0u8; 2];
+        file.read_exact(&mut version)?;
+        let version = u16::from_be_bytes(version);
+        if version != VERSION {
+            return Err(io::Error::other(BinFileError::InvalidVersion {
+                filename: filename.to_string_lossy().to_string(),
+                expected: VERSION,
+                actual: version,
+            }));
+        }
+        let mut checksum = [0u8; 4];
+        file.read_exact(&mut checksum)?;
+        let expected = u32::from_be_bytes(checksum);
+
+        let mut payload = Vec::new();
+        file.read_to_end(&mut payload)?;
+        let actual = crc32fast::hash(&payload);
+        if actual != expected {
+            return Err(io::Error::other(BinFileError::ChecksumMismatch {
+                filename: filename.to_string_lossy().to_string(),
+                expected,
+                actual,
+            }));
+        }
+
+        file.seek(SeekFrom::Start(CHECKSUM_HEADER_LEN as u64))?;
+        Ok(version)
+    }
+
+    /// Recomputes the CRC32 of the payload and rewrites it into the checksum field, preserving the
+    /// stream's current position.
+    fn rewrite_checksum(&mut self) -> io::Result<()> {
+        let position = self.file.stream_position()?;
+        self.file.seek(SeekFrom::Start(CHECKSUM_HEADER_LEN as u64))?;
+        let mut payload = Vec::new();
+        self.file.read_to_end(&mut payload)?;
+        let checksum = crc32fast::hash(&payload);
+        self.file.seek(SeekFrom::Start(HEADER_LEN as u64))?;
+        self.file.write_all(&checksum.to_be_bytes())?;
+        self.file.seek(SeekFrom::Start(position))?;
         Ok(())
     }
 }
@@ -159,6 +594,308 @@ pub enum BinFileError {
         #[allow(missing_docs)]
         actual: u16,
     },
+    /// checksum mismatch: expected {expected:#010x}, got {actual:#010x} in file '{filename}'.
+    ChecksumMismatch {
+        #[allow(missing_docs)]
+        filename: String,
+        #[allow(missing_docs)]
+        expected: u32,
+        #[allow(missing_docs)]
+        actual: u32,
+    },
+    /// embedded metadata rejected as incompatible in file '{filename}'.
+    IncompatibleMetadata {
+        #[allow(missing_docs)]
+        filename: String,
+    },
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Byte order used by [`EndianBinFile`] to serialize and compare a header's magic number and
+/// version.
+///
+/// This is a sealed trait; the only implementors are [`BigEndian`] and [`LittleEndian`].
+pub trait Endian: sealed::Sealed + std::fmt::Debug {
+    /// Encodes a 16-bit version number using this byte order.
+    fn encode_u16(value: u16) -> [u8; 2];
+    /// Decodes a 16-bit version number that was encoded using this byte order.
+    fn decode_u16(bytes: [u8; 2]) -> u16;
+    /// Encodes the first `len` bytes of a 64-bit magic number serialized in this byte order: the
+    /// high bytes for [`BigEndian`], the low bytes for [`LittleEndian`].
+    fn encode_magic(value: u64, len: usize) -> Vec<u8>;
+    /// Decodes a magic number of `bytes.len()` bytes that was encoded using this byte order.
+    fn decode_magic(bytes: &[u8]) -> u64;
+}
+
+/// Big-endian byte order — the byte order [`BinFile`] uses for its 8-byte magic and version.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct BigEndian;
+
+impl sealed::Sealed for BigEndian {}
+
+impl Endian for BigEndian {
+    fn encode_u16(value: u16) -> [u8; 2] { value.to_be_bytes() }
+
+    fn decode_u16(bytes: [u8; 2]) -> u16 { u16::from_be_bytes(bytes) }
+
+    // Big-endian magics follow the same construction idiom as [`BinFile`]'s fixed 8-byte magic
+    // (`u64::from_be_bytes(*b"MYMAGIC!")`), extended with trailing zero bytes for a shorter
+    // magic, e.g. `u64::from_be_bytes(*b"MMPD\0\0\0\0")`. That idiom places the magic in the
+    // *high* bytes of the u64, so those are the bytes read from and written to the file.
+    fn encode_magic(value: u64, len: usize) -> Vec<u8> { value.to_be_bytes()[..len].to_vec() }
+
+    fn decode_magic(bytes: &[u8]) -> u64 {
+        let mut buf = [0u8; 8];
+        buf[..bytes.len()].copy_from_slice(bytes);
+        u64::from_be_bytes(buf)
+    }
+}
+
+impl BigEndian {
+    /// Builds a `MAGIC` constant for [`EndianBinFile`]/[`BinFileBE`] from a magic shorter than 8
+    /// bytes, placing it in the high bytes that [`encode_magic`](Endian::encode_magic) and
+    /// [`decode_magic`](Endian::decode_magic) read from and write to. Prefer this over padding a
+    /// byte-string literal by hand, since it removes the need to reason about which end of the
+    /// `u64` the bytes belong in.
+    ///
+    /// ```
+    /// use binfile::{BigEndian, BinFileBE};
+    ///
+    /// const MMPD_MAGIC: u64 = BigEndian::pack_magic(*b"MMPD");
+    /// BinFileBE::<MMPD_MAGIC, 1, 4>::create("target/test_mmpd_packed").unwrap();
+    /// ```
+    pub const fn pack_magic<const N: usize>(bytes: [u8; N]) -> u64 {
+        let mut buf = [0u8; 8];
+        let mut i = 0;
+        while i < N {
+            buf[i] = bytes[i];
+            i += 1;
+        }
+        u64::from_be_bytes(buf)
+    }
+}
+
+/// Little-endian byte order, as used by measureme- and rustc incremental cache-style formats.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct LittleEndian;
+
+impl sealed::Sealed for LittleEndian {}
+
+impl Endian for LittleEndian {
+    fn encode_u16(value: u16) -> [u8; 2] { value.to_le_bytes() }
+
+    fn decode_u16(bytes: [u8; 2]) -> u16 { u16::from_le_bytes(bytes) }
+
+    fn encode_magic(value: u64, len: usize) -> Vec<u8> { value.to_le_bytes()[..len].to_vec() }
+
+    fn decode_magic(bytes: &[u8]) -> u64 {
+        let mut buf = [0u8; 8];
+        buf[..bytes.len()].copy_from_slice(bytes);
+        u64::from_le_bytes(buf)
+    }
+}
+
+impl LittleEndian {
+    /// Builds a `MAGIC` constant for [`EndianBinFile`]/[`BinFileLE`] from a magic shorter than 8
+    /// bytes, placing it in the low bytes that [`encode_magic`](Endian::encode_magic) and
+    /// [`decode_magic`](Endian::decode_magic) read from and write to. Prefer this over padding a
+    /// byte-string literal by hand, since it removes the need to reason about which end of the
+    /// `u64` the bytes belong in.
+    ///
+    /// ```
+    /// use binfile::{BinFileLE, LittleEndian};
+    ///
+    /// const MMPD_MAGIC: u64 = LittleEndian::pack_magic(*b"MMPD");
+    /// BinFileLE::<MMPD_MAGIC, 1, 4>::create("target/test_mmpd_le_packed").unwrap();
+    /// ```
+    pub const fn pack_magic<const N: usize>(bytes: [u8; N]) -> u64 {
+        let mut buf = [0u8; 8];
+        let mut i = 0;
+        while i < N {
+            buf[i] = bytes[i];
+            i += 1;
+        }
+        u64::from_le_bytes(buf)
+    }
+}
+
+/// Binary file like [`BinFile`], but with a configurable header byte order and magic width.
+///
+/// [`BinFile`] hardcodes an 8-byte big-endian magic and a big-endian version, which cannot
+/// interoperate with formats such as measureme's or the rustc incremental cache's, which use
+/// 4-byte magics (e.g. `b"MMPD"`) with little-endian version fields. `EndianBinFile` generalizes
+/// both the byte order, via the `E` parameter, and the magic width in bytes, via `MAGIC_LEN`,
+/// computing its header length as `MAGIC_LEN + 2` rather than the fixed `10`.
+///
+/// Prefer the [`BinFileBE`] and [`BinFileLE`] aliases over naming this type directly.
+///
+/// Ideally `MAGIC_LEN` and the magic would be a single `[u8; MAGIC_LEN]` const parameter, which
+/// would make the byte order irrelevant to callers; stable Rust only allows integers, `bool`, and
+/// `char` as the type of a const generic parameter, so `MAGIC` stays a `u64` alongside the
+/// separate `MAGIC_LEN` width. For a magic shorter than 8 bytes, build the `MAGIC` constant with
+/// [`BigEndian::pack_magic`]/[`LittleEndian::pack_magic`] rather than padding the byte-string
+/// literal by hand — they place the bytes at whichever end `E` reads from and write to, so
+/// callers never need to reason about high vs. low bytes themselves:
+///
+/// ```
+/// use binfile::{BigEndian, BinFileBE};
+///
+/// const MMPD_MAGIC: u64 = BigEndian::pack_magic(*b"MMPD");
+///
+/// BinFileBE::<MMPD_MAGIC, 1, 4>::create("target/test_mmpd").unwrap();
+/// ```
+///
+/// `MAGIC_LEN` must be between 1 and 8, since it has to fit within the 8 bytes of the `MAGIC`
+/// constant; an out-of-range value fails to compile rather than panicking at runtime:
+///
+/// ```compile_fail
+/// use binfile::BinFileBE;
+///
+/// // MAGIC_LEN of 12 doesn't fit in a u64 and fails to compile.
+/// BinFileBE::<0, 1, 12>::create("target/test_invalid_magic_len").unwrap();
+/// ```
+#[derive(Debug)]
+pub struct EndianBinFile<
+    const MAGIC: u64,
+    const VERSION: u16,
+    const MAGIC_LEN: usize = 8,
+    E: Endian = BigEndian,
+> {
+    file: File,
+    version: u16,
+    _endian: PhantomData<E>,
+}
+
+/// [`EndianBinFile`] using big-endian byte order, the same layout as [`BinFile`] but with a
+/// configurable magic width.
+pub type BinFileBE<const MAGIC: u64, const VERSION: u16, const MAGIC_LEN: usize = 8> =
+    EndianBinFile<MAGIC, VERSION, MAGIC_LEN, BigEndian>;
+
+/// [`EndianBinFile`] using little-endian byte order, as used by measureme- and rustc incremental
+/// cache-style formats.
+pub type BinFileLE<const MAGIC: u64, const VERSION: u16, const MAGIC_LEN: usize = 8> =
+    EndianBinFile<MAGIC, VERSION, MAGIC_LEN, LittleEndian>;
+
+impl<const MAGIC: u64, const VERSION: u16, const MAGIC_LEN: usize, E: Endian> Deref
+    for EndianBinFile<MAGIC, VERSION, MAGIC_LEN, E>
+{
+    type Target = File;
+
+    fn deref(&self) -> &Self::Target { &self.file }
+}
+
+impl<const MAGIC: u64, const VERSION: u16, const MAGIC_LEN: usize, E: Endian> DerefMut
+    for EndianBinFile<MAGIC, VERSION, MAGIC_LEN, E>
+{
+    fn deref_mut(&mut self) -> &mut Self::Target { &mut self.file }
+}
+
+impl<const MAGIC: u64, const VERSION: u16, const MAGIC_LEN: usize, E: Endian> Read
+    for EndianBinFile<MAGIC, VERSION, MAGIC_LEN, E>
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> { self.file.read(buf) }
+}
+
+impl<const MAGIC: u64, const VERSION: u16, const MAGIC_LEN: usize, E: Endian> Write
+    for EndianBinFile<MAGIC, VERSION, MAGIC_LEN, E>
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> { self.file.write(buf) }
+    fn flush(&mut self) -> io::Result<()> { self.file.flush() }
+}
+
+impl<const MAGIC: u64, const VERSION: u16, const MAGIC_LEN: usize, E: Endian>
+    EndianBinFile<MAGIC, VERSION, MAGIC_LEN, E>
+{
+    /// The magical byte octet, taken from the generic parameter of the type.
+    pub const MAGIC: u64 = MAGIC;
+
+    /// The version number, taken from the generic parameter of the type.
+    pub const VERSION: u16 = VERSION;
+
+    /// Compile-time check that `MAGIC_LEN` fits within the 8 bytes of the `MAGIC` constant.
+    ///
+    /// Referenced at the start of every constructor so that an out-of-range `MAGIC_LEN` fails
+    /// with this clear message at monomorphization time, rather than as an opaque slice index
+    /// out-of-range panic from deep inside [`Endian::encode_magic`].
+    const ASSERT_VALID_MAGIC_LEN: () =
+        assert!(MAGIC_LEN >= 1 && MAGIC_LEN <= 8, "EndianBinFile: MAGIC_LEN must be between 1 and 8");
+
+    /// The version that was actually found in the file header.
+    pub fn version(&self) -> u16 { self.version }
+
+    /// Creates the file, the same way as [`BinFile::create`] does, using `E`'s byte order and a
+    /// `MAGIC_LEN`-byte magic. The produced file stream will start at byte offset `MAGIC_LEN + 2`.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        let () = Self::ASSERT_VALID_MAGIC_LEN;
+        let mut file =
+            OpenOptions::new().read(true).write(true).create(true).truncate(true).open(path)?;
+        file.write_all(&E::encode_magic(MAGIC, MAGIC_LEN))?;
+        file.write_all(&E::encode_u16(VERSION))?;
+        Ok(Self { file, version: VERSION, _endian: PhantomData })
+    }
+
+    /// Creates a new file, the same way as [`BinFile::create_new`] does, using `E`'s byte order
+    /// and a `MAGIC_LEN`-byte magic. The produced file stream will start at byte offset
+    /// `MAGIC_LEN + 2`.
+    pub fn create_new(path: impl AsRef<Path>) -> io::Result<Self> {
+        let () = Self::ASSERT_VALID_MAGIC_LEN;
+        let mut file = OpenOptions::new().read(true).write(true).create_new(true).open(path)?;
+        file.write_all(&E::encode_magic(MAGIC, MAGIC_LEN))?;
+        file.write_all(&E::encode_u16(VERSION))?;
+        Ok(Self { file, version: VERSION, _endian: PhantomData })
+    }
+
+    /// Attempts to open a file in read-only mode, the same way as [`BinFile::open`] does, using
+    /// `E`'s byte order and a `MAGIC_LEN`-byte magic.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let () = Self::ASSERT_VALID_MAGIC_LEN;
+        let path = path.as_ref();
+        let mut file = Self { file: File::open(path)?, version: 0, _endian: PhantomData };
+        file.check(path)?;
+        Ok(file)
+    }
+
+    /// Attempts to open a file in read-write mode, the same way as [`BinFile::open_rw`] does,
+    /// using `E`'s byte order and a `MAGIC_LEN`-byte magic.
+    pub fn open_rw(path: impl AsRef<Path>) -> io::Result<Self> {
+        let () = Self::ASSERT_VALID_MAGIC_LEN;
+        let path = path.as_ref();
+        let mut file = Self {
+            file: OpenOptions::new().read(true).write(true).open(path)?,
+            version: 0,
+            _endian: PhantomData,
+        };
+        file.check(path)?;
+        Ok(file)
+    }
+
+    fn check(&mut self, filename: &Path) -> io::Result<()> {
+        let mut magic = vec![0u8; MAGIC_LEN];
+        self.read_exact(&mut magic)?;
+        let actual = E::decode_magic(&magic);
+        if actual != MAGIC {
+            return Err(io::Error::other(BinFileError::InvalidMagic {
+                filename: filename.to_string_lossy().to_string(),
+                expected: MAGIC,
+                actual,
+            }));
+        }
+        let mut version = [0u8; 2];
+        self.read_exact(&mut version)?;
+        let version = E::decode_u16(version);
+        if version != VERSION {
+            return Err(io::Error::other(BinFileError::InvalidVersion {
+                filename: filename.to_string_lossy().to_string(),
+                expected: VERSION,
+                actual: version,
+            }));
+        }
+        self.version = version;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -253,4 +990,213 @@ mod tests {
             actual: 1,
         });
     }
+
+    #[test]
+    fn open_compatible_accepts_older_version() {
+        const MY_MAGIC: u64 = u64::from_be_bytes(*b"MYMAGIC!");
+        fs::remove_file("target/test_compat1").ok();
+        let mut file = BinFile::<MY_MAGIC, 1>::create("target/test_compat1").unwrap();
+        file.write_all(b"hello world").unwrap();
+        file.flush().unwrap();
+
+        let file = BinFile::<MY_MAGIC, 2>::open_compatible("target/test_compat1", 1..=2).unwrap();
+        assert_eq!(file.version(), 1);
+    }
+
+    #[test]
+    fn open_compatible_rejects_out_of_range_version() {
+        const MY_MAGIC: u64 = u64::from_be_bytes(*b"MYMAGIC!");
+        fs::remove_file("target/test_compat2").ok();
+        let mut file = BinFile::<MY_MAGIC, 1>::create("target/test_compat2").unwrap();
+        file.write_all(b"hello world").unwrap();
+        file.flush().unwrap();
+
+        let err =
+            BinFile::<MY_MAGIC, 3>::open_compatible("target/test_compat2", 2..=3).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Other);
+        assert_eq!(err.downcast::<BinFileError>().unwrap(), BinFileError::InvalidVersion {
+            filename: "target/test_compat2".to_string(),
+            expected: 3,
+            actual: 1,
+        });
+    }
+
+    #[cfg(feature = "memmap")]
+    #[test]
+    fn mmap_after_create() {
+        const MY_MAGIC: u64 = u64::from_be_bytes(*b"MYMAGIC!");
+        let mut file = BinFile::<MY_MAGIC, 1>::create("target/test6").unwrap();
+        file.write_all(b"hello world").unwrap();
+        file.flush().unwrap();
+
+        let map = file.mmap().unwrap();
+        assert_eq!(map.version(), 1);
+        assert_eq!(&*map, b"hello world");
+    }
+
+    #[cfg(feature = "memmap")]
+    #[test]
+    fn mmap_after_open() {
+        const MY_MAGIC: u64 = u64::from_be_bytes(*b"MYMAGIC!");
+        let mut file = BinFile::<MY_MAGIC, 1>::create("target/test7").unwrap();
+        file.write_all(b"hello world").unwrap();
+        file.flush().unwrap();
+        drop(file);
+
+        let file = BinFile::<MY_MAGIC, 1>::open("target/test7").unwrap();
+        let map = file.mmap().unwrap();
+        assert_eq!(&*map, b"hello world");
+    }
+
+    #[test]
+    fn checksummed_round_trip() {
+        const MY_MAGIC: u64 = u64::from_be_bytes(*b"MYMAGIC!");
+        fs::remove_file("target/test_crc1").ok();
+        let mut file = ChecksummedBinFile::<MY_MAGIC, 1>::create("target/test_crc1").unwrap();
+        file.write_all(b"hello world").unwrap();
+        file.flush().unwrap();
+        drop(file);
+
+        let mut file = ChecksummedBinFile::<MY_MAGIC, 1>::open("target/test_crc1").unwrap();
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf).unwrap();
+        assert_eq!(buf, b"hello world");
+    }
+
+    #[test]
+    fn checksummed_detects_corruption() {
+        const MY_MAGIC: u64 = u64::from_be_bytes(*b"MYMAGIC!");
+        fs::remove_file("target/test_crc2").ok();
+        let mut file = ChecksummedBinFile::<MY_MAGIC, 1>::create("target/test_crc2").unwrap();
+        file.write_all(b"hello world").unwrap();
+        file.flush().unwrap();
+        drop(file);
+
+        let mut raw = fs::read("target/test_crc2").unwrap();
+        *raw.last_mut().unwrap() ^= 0xFF;
+        fs::write("target/test_crc2", &raw).unwrap();
+
+        let err = ChecksummedBinFile::<MY_MAGIC, 1>::open("target/test_crc2").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Other);
+        assert!(matches!(
+            err.downcast::<BinFileError>().unwrap(),
+            BinFileError::ChecksumMismatch { .. }
+        ));
+    }
+
+    #[test]
+    fn peek_header_reads_without_validating() {
+        const MY_MAGIC: u64 = u64::from_be_bytes(*b"MYMAGIC!");
+        let mut file = BinFile::<MY_MAGIC, 7>::create("target/test_peek1").unwrap();
+        file.write_all(b"hello world").unwrap();
+        file.flush().unwrap();
+
+        let (magic, version) = peek_header("target/test_peek1").unwrap();
+        assert_eq!(magic, MY_MAGIC);
+        assert_eq!(version, 7);
+    }
+
+    #[test]
+    fn peek_header_too_short_errors() {
+        fs::write("target/test_peek2", b"short").unwrap();
+        let err = peek_header("target/test_peek2").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn raw_bin_file_try_open_exposes_unknown_header() {
+        const MY_MAGIC: u64 = u64::from_be_bytes(*b"MYMAGIC!");
+        let mut file = BinFile::<MY_MAGIC, 3>::create("target/test_raw1").unwrap();
+        file.write_all(b"hello world").unwrap();
+        file.flush().unwrap();
+
+        let mut raw = RawBinFile::try_open("target/test_raw1").unwrap();
+        assert_eq!(raw.magic(), MY_MAGIC);
+        assert_eq!(raw.version(), 3);
+        let mut buf = Vec::new();
+        raw.read_to_end(&mut buf).unwrap();
+        assert_eq!(buf, b"hello world");
+    }
+
+    #[test]
+    fn metadata_round_trips() {
+        const MY_MAGIC: u64 = u64::from_be_bytes(*b"MYMAGIC!");
+        fs::remove_file("target/test_meta1").ok();
+        let mut file =
+            BinFile::<MY_MAGIC, 1, true>::create_with_meta("target/test_meta1", b"1.2.3").unwrap();
+        file.write_all(b"hello world").unwrap();
+        file.flush().unwrap();
+        drop(file);
+
+        let mut file = BinFile::<MY_MAGIC, 1, true>::open("target/test_meta1").unwrap();
+        assert_eq!(file.metadata(), b"1.2.3");
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf).unwrap();
+        assert_eq!(buf, b"hello world");
+    }
+
+    #[test]
+    fn create_with_meta_readable_without_reopening() {
+        const MY_MAGIC: u64 = u64::from_be_bytes(*b"MYMAGIC!");
+        fs::remove_file("target/test_meta3").ok();
+        let mut file =
+            BinFile::<MY_MAGIC, 1, true>::create_with_meta("target/test_meta3", b"1.2.3").unwrap();
+        file.write_all(b"hello world").unwrap();
+        file.flush().unwrap();
+
+        file.seek(SeekFrom::Start((HEADER_LEN + 2 + b"1.2.3".len()) as u64)).unwrap();
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf).unwrap();
+        assert_eq!(buf, b"hello world");
+    }
+
+    #[test]
+    fn open_validated_rejects_incompatible_metadata() {
+        const MY_MAGIC: u64 = u64::from_be_bytes(*b"MYMAGIC!");
+        fs::remove_file("target/test_meta2").ok();
+        BinFile::<MY_MAGIC, 1, true>::create_with_meta("target/test_meta2", b"1.2.3").unwrap();
+
+        let err = BinFile::<MY_MAGIC, 1, true>::open_validated("target/test_meta2", |meta| {
+            meta == b"9.9.9"
+        })
+        .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Other);
+        assert_eq!(err.downcast::<BinFileError>().unwrap(), BinFileError::IncompatibleMetadata {
+            filename: "target/test_meta2".to_string(),
+        });
+    }
+
+    #[test]
+    fn endian_be_short_magic_round_trips() {
+        const MMPD_MAGIC: u64 = BigEndian::pack_magic(*b"MMPD");
+        fs::remove_file("target/test_endian1").ok();
+        let mut file = BinFileBE::<MMPD_MAGIC, 1, 4>::create("target/test_endian1").unwrap();
+        file.write_all(b"hello world").unwrap();
+        file.flush().unwrap();
+
+        let check = fs::read("target/test_endian1").unwrap();
+        assert_eq!(&check[..6], b"MMPD\x00\x01");
+
+        let mut file = BinFileBE::<MMPD_MAGIC, 1, 4>::open("target/test_endian1").unwrap();
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf).unwrap();
+        assert_eq!(buf, b"hello world");
+    }
+
+    #[test]
+    fn endian_le_short_magic_round_trips() {
+        const MMPD_MAGIC: u64 = LittleEndian::pack_magic(*b"MMPD");
+        fs::remove_file("target/test_endian2").ok();
+        let mut file = BinFileLE::<MMPD_MAGIC, 1, 4>::create("target/test_endian2").unwrap();
+        file.write_all(b"hello world").unwrap();
+        file.flush().unwrap();
+
+        let check = fs::read("target/test_endian2").unwrap();
+        assert_eq!(&check[..6], b"MMPD\x01\x00");
+
+        let mut file = BinFileLE::<MMPD_MAGIC, 1, 4>::open("target/test_endian2").unwrap();
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf).unwrap();
+        assert_eq!(buf, b"hello world");
+    }
 }